@@ -1,34 +1,114 @@
 use crate::bigtable::{Error, Result, RowCell, RowKey};
 use crate::google::bigtable::v2::read_rows_response::cell_chunk::RowStatus;
-use crate::google::bigtable::v2::ReadRowsResponse;
-use log::{trace, warn};
+use crate::google::bigtable::v2::row_range::{EndKey, StartKey};
+use crate::google::bigtable::v2::row_set::RowOrRowRanges;
+use crate::google::bigtable::v2::{ReadRowsRequest, ReadRowsResponse, RowRange, RowSet};
+use async_stream::try_stream;
+use backoff::backoff::Backoff;
+use backoff::ExponentialBackoff;
+use futures::stream::Stream;
+use futures::TryStreamExt;
+use log::{debug, trace, warn};
+use prost::Message;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::io::Read;
 use std::time::{Duration, Instant};
-use tonic::Streaming;
+use tonic::{Status, Streaming};
 
-/// As each `CellChunk` could be only part of a cell, this method reorganize multiple `CellChunk`
-/// from multiple `ReadRowsResponse` into a `Vec<(RowKey, Vec<RowCell>)>`.
-pub async fn decode_read_rows_response(
-    timeout: &Option<Duration>,
-    mut rrr: Streaming<ReadRowsResponse>,
-) -> Result<Vec<(RowKey, Vec<RowCell>)>> {
-    let mut rows: Vec<(RowKey, Vec<RowCell>)> = vec![];
-
-    let mut row_key = None;
-    let mut row_data: Vec<RowCell> = vec![];
+/// Compression method tagged onto a cell value by a `compress_best`-style writer.
+///
+/// A stored value is framed as a single leading tag byte followed by the (possibly compressed)
+/// payload, so a reader can decompress without being told in advance which method was used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionMethod {
+    None,
+    Gzip,
+    Bzip2,
+    Zstd,
+}
 
-    let mut cell_family_name = None;
-    let mut cell_name = None;
-    let mut cell_timestamp = 0;
-    let mut cell_value = vec![];
+impl CompressionMethod {
+    fn from_tag(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(CompressionMethod::None),
+            1 => Some(CompressionMethod::Gzip),
+            2 => Some(CompressionMethod::Bzip2),
+            3 => Some(CompressionMethod::Zstd),
+            _ => None,
+        }
+    }
+}
 
-    let started = Instant::now();
+/// Decompresses a cell value written with the `CompressionMethod` framing above.
+///
+/// The leading byte is read as the method tag and the rest as the payload. A missing or
+/// unrecognized tag (including an empty value) is treated as uncompressed, so values written
+/// before compression was introduced are passed through unchanged, unless their first byte
+/// happens to be one of the tags 0-3, in which case it is read as a tag.
+fn decompress(value: Vec<u8>) -> Result<Vec<u8>> {
+    let (tag, payload) = match value.split_first() {
+        Some((tag, payload)) => (*tag, payload),
+        None => return Ok(value),
+    };
 
-    while let Some(res) = rrr.message().await? {
-        if let Some(timeout) = timeout.as_ref() {
-            if Instant::now().duration_since(started) > *timeout {
-                return Err(Error::TimeoutError(timeout.as_secs()));
-            }
+    match CompressionMethod::from_tag(tag) {
+        // An unrecognized tag means `value` was never tagged at all, so the whole thing is the
+        // payload. An explicit `None` tag, though, still has that leading byte to strip.
+        None => Ok(value),
+        Some(CompressionMethod::None) => Ok(payload.to_vec()),
+        Some(CompressionMethod::Gzip) => {
+            let mut out = Vec::new();
+            flate2::read::GzDecoder::new(payload)
+                .read_to_end(&mut out)
+                .map_err(|e| Error::DecompressionError(e.to_string()))?;
+            Ok(out)
         }
+        Some(CompressionMethod::Bzip2) => {
+            let mut out = Vec::new();
+            bzip2::read::BzDecoder::new(payload)
+                .read_to_end(&mut out)
+                .map_err(|e| Error::DecompressionError(e.to_string()))?;
+            Ok(out)
+        }
+        Some(CompressionMethod::Zstd) => {
+            zstd::stream::decode_all(payload).map_err(|e| Error::DecompressionError(e.to_string()))
+        }
+    }
+}
+
+/// Reassembles `CellChunk`s spread across multiple `ReadRowsResponse` messages into complete
+/// rows, one chunk at a time.
+///
+/// This carries the chunk-reassembly accumulator state so it can be driven incrementally: by
+/// [`read_rows_stream`] (and so [`decode_read_rows_response`]) over a single stream, and by
+/// [`read_rows_with_retry`] across reconnects. The extra `last_committed_row_key` and
+/// `rows_emitted` fields track progress so a retry can resume the scan without re-emitting rows
+/// that already committed.
+#[derive(Default)]
+struct RowChunkDecoder {
+    row_key: Option<RowKey>,
+    row_data: Vec<RowCell>,
+
+    cell_family_name: Option<String>,
+    cell_name: Option<Vec<u8>>,
+    cell_timestamp: i64,
+    cell_value: Vec<u8>,
+
+    last_committed_row_key: Option<RowKey>,
+    rows_emitted: i64,
+}
+
+impl RowChunkDecoder {
+    /// Feeds one `ReadRowsResponse` into the state machine, appending any rows it completes to
+    /// `out` in order. Returns the number of rows newly committed by this response.
+    fn feed(
+        &mut self,
+        res: ReadRowsResponse,
+        decompress: bool,
+        out: &mut Vec<(RowKey, Vec<RowCell>)>,
+    ) -> Result<usize> {
+        let mut committed = 0;
         for (i, mut chunk) in res.chunks.into_iter().enumerate() {
             // The comments for `read_rows_response::CellChunk` provide essential details for
             // understanding how the below decoding works...
@@ -36,28 +116,33 @@ pub async fn decode_read_rows_response(
 
             // Starting a new row?
             if !chunk.row_key.is_empty() {
-                row_key = Some(chunk.row_key);
+                self.row_key = Some(chunk.row_key);
             }
 
             // Starting a new cell? A new cell will have a qualifier and a family
             if let Some(chunk_qualifier) = chunk.qualifier {
                 // New cell begins. Check whether previous cell_name exist, if so then it means
                 // the cell_value is not empty and previous cell is not closed up. So close up the previous cell.
-                if let Some(cell_name) = cell_name {
+                if let Some(cell_name) = self.cell_name.take() {
+                    let taken = std::mem::take(&mut self.cell_value);
+                    let value = if decompress {
+                        self::decompress(taken)?
+                    } else {
+                        taken
+                    };
                     let row_cell = RowCell {
-                        family_name: cell_family_name.take().unwrap_or("".to_owned()),
+                        family_name: self.cell_family_name.take().unwrap_or("".to_owned()),
                         qualifier: cell_name,
-                        value: cell_value,
-                        timestamp_micros: cell_timestamp,
+                        value,
+                        timestamp_micros: self.cell_timestamp,
                     };
-                    row_data.push(row_cell);
-                    cell_value = vec![];
+                    self.row_data.push(row_cell);
                 }
-                cell_name = Some(chunk_qualifier);
-                cell_family_name = chunk.family_name;
-                cell_timestamp = chunk.timestamp_micros;
+                self.cell_name = Some(chunk_qualifier);
+                self.cell_family_name = chunk.family_name;
+                self.cell_timestamp = chunk.timestamp_micros;
             }
-            cell_value.append(&mut chunk.value);
+            self.cell_value.append(&mut chunk.value);
 
             // End of a row?
             match chunk.row_status {
@@ -67,32 +152,596 @@ pub async fn decode_read_rows_response(
                 }
                 Some(RowStatus::CommitRow(_)) => {
                     // End of a row, closing up the cell, then close this row
-                    if let Some(cell_name) = cell_name.take() {
+                    if let Some(cell_name) = self.cell_name.take() {
+                        let value = if decompress {
+                            self::decompress(std::mem::take(&mut self.cell_value))?
+                        } else {
+                            std::mem::take(&mut self.cell_value)
+                        };
                         let row_cell = RowCell {
-                            family_name: cell_family_name.take().unwrap_or("".to_owned()),
+                            family_name: self.cell_family_name.take().unwrap_or("".to_owned()),
                             qualifier: cell_name,
-                            value: cell_value,
-                            timestamp_micros: cell_timestamp,
+                            value,
+                            timestamp_micros: self.cell_timestamp,
                         };
-                        row_data.push(row_cell);
-                        cell_value = vec![];
+                        self.row_data.push(row_cell);
                     } else {
                         warn!("Row ended with cell_name=None. This should not happen.")
                     }
 
-                    if let Some(row_key) = row_key.take() {
-                        rows.push((row_key, row_data));
-                        row_data = vec![];
+                    if let Some(row_key) = self.row_key.take() {
+                        self.last_committed_row_key = Some(row_key.clone());
+                        self.rows_emitted += 1;
+                        committed += 1;
+                        out.push((row_key, std::mem::take(&mut self.row_data)));
                     }
                 }
                 Some(RowStatus::ResetRow(_)) => {
                     // ResetRow indicates that the client should drop all previous chunks for
                     // `row_key`, as it will be re-read from the beginning.
-                    row_key = None;
-                    row_data = vec![];
+                    self.discard_partial_row();
+                }
+            }
+        }
+        Ok(committed)
+    }
+
+    /// Drops any uncommitted row/cell state. Used on `ResetRow` and when a retry is about to
+    /// reconnect: the server will resend the interrupted row from scratch, so whatever partial
+    /// chunks we'd already accumulated for it must not be appended to.
+    fn discard_partial_row(&mut self) {
+        self.row_key = None;
+        self.row_data = vec![];
+        self.cell_family_name = None;
+        self.cell_name = None;
+        self.cell_timestamp = 0;
+        self.cell_value = vec![];
+    }
+}
+
+/// Streams `(RowKey, Vec<RowCell>)` pairs as soon as each row's `CommitRow` chunk arrives,
+/// rather than buffering the whole scan into memory like [`decode_read_rows_response`] does.
+/// Callers that want to forward rows onward (e.g. to another writer) can consume this directly
+/// instead of waiting for a multi-gigabyte scan to finish.
+///
+/// Driven by the same [`RowChunkDecoder`] state machine, so a `ResetRow` mid-stream still
+/// discards uncommitted partial rows correctly — they are simply never yielded.
+pub fn read_rows_stream(
+    timeout: Option<Duration>,
+    mut rrr: Streaming<ReadRowsResponse>,
+    decompress: bool,
+) -> impl Stream<Item = Result<(RowKey, Vec<RowCell>)>> {
+    try_stream! {
+        let mut decoder = RowChunkDecoder::default();
+        let started = Instant::now();
+
+        while let Some(res) = rrr.message().await? {
+            if let Some(timeout) = timeout.as_ref() {
+                if Instant::now().duration_since(started) > *timeout {
+                    Err(Error::TimeoutError(timeout.as_secs()))?;
                 }
             }
+            let mut completed = vec![];
+            decoder.feed(res, decompress, &mut completed)?;
+            for row in completed {
+                yield row;
+            }
         }
     }
-    Ok(rows)
+}
+
+/// As each `CellChunk` could be only part of a cell, this method reorganize multiple `CellChunk`
+/// from multiple `ReadRowsResponse` into a `Vec<(RowKey, Vec<RowCell>)>`.
+///
+/// When `decompress` is `true`, each cell's value is passed through [`decompress`] before being
+/// stored, so rows written by a `compress_best`-style writer come back as plaintext.
+///
+/// A thin, memory-buffering adapter over [`read_rows_stream`]; prefer that function directly for
+/// large scans.
+pub async fn decode_read_rows_response(
+    timeout: &Option<Duration>,
+    rrr: Streaming<ReadRowsResponse>,
+    decompress: bool,
+) -> Result<Vec<(RowKey, Vec<RowCell>)>> {
+    read_rows_stream(*timeout, rrr, decompress)
+        .try_collect()
+        .await
+}
+
+/// Returns `true` if `status` represents a transient gRPC failure worth retrying (as opposed to,
+/// e.g., an invalid argument that will never succeed no matter how many times it's resent).
+fn is_retriable(status: &Status) -> bool {
+    use tonic::Code::*;
+    matches!(
+        status.code(),
+        Unavailable | DeadlineExceeded | Aborted | Internal | ResourceExhausted
+    )
+}
+
+/// Narrows a single `RowRange` so it resumes strictly after `cursor`, or drops it entirely if
+/// the scan had already passed its end key.
+///
+/// Bigtable row ranges are lexicographic: a range whose end key is at or before `cursor` has
+/// nothing left to contribute, and a range whose start is at or before `cursor` is the one the
+/// scan was part-way through when it failed, so only that one needs its start key rewritten.
+/// Ranges entirely ahead of `cursor` are returned unchanged.
+fn resume_row_range(range: RowRange, cursor: &RowKey) -> Option<RowRange> {
+    let already_ended = match &range.end_key {
+        Some(EndKey::EndKeyClosed(k)) | Some(EndKey::EndKeyOpen(k)) => {
+            k.as_slice() <= cursor.as_slice()
+        }
+        None => false,
+    };
+    if already_ended {
+        return None;
+    }
+
+    let starts_at_or_before_cursor = match &range.start_key {
+        Some(StartKey::StartKeyClosed(k)) | Some(StartKey::StartKeyOpen(k)) => {
+            k.as_slice() <= cursor.as_slice()
+        }
+        None => true,
+    };
+
+    if starts_at_or_before_cursor {
+        Some(RowRange {
+            start_key: Some(StartKey::StartKeyOpen(cursor.clone())),
+            end_key: range.end_key,
+        })
+    } else {
+        Some(range)
+    }
+}
+
+/// Rebuilds `request`'s `RowSet` so the scan resumes strictly after `last_committed_row_key`,
+/// and decrements `rows_limit` by the number of rows already emitted.
+///
+/// Returns `None` if `rows_emitted` has already met or exceeded the original `rows_limit`, i.e.
+/// there is nothing left for a resumed request to fetch. A `rows_limit` of 0 means no limit and
+/// is left as it is.
+fn resume_request(
+    mut request: ReadRowsRequest,
+    last_committed_row_key: &RowKey,
+    rows_emitted: i64,
+) -> Option<ReadRowsRequest> {
+    if request.rows_limit > 0 {
+        let remaining = request.rows_limit - rows_emitted;
+        if remaining <= 0 {
+            return None;
+        }
+        request.rows_limit = remaining;
+    }
+
+    request.rows = Some(match request.rows.take() {
+        // No explicit row set means "the whole table": resume with a single open range.
+        None
+        | Some(RowSet {
+            row_or_row_ranges: None,
+        }) => RowSet {
+            row_or_row_ranges: Some(RowOrRowRanges::RowRanges(vec![RowRange {
+                start_key: Some(StartKey::StartKeyOpen(last_committed_row_key.clone())),
+                end_key: None,
+            }])),
+        },
+        // One or more row ranges: trim whichever range the scan was in progress on, drop any
+        // ranges entirely before the cursor, and keep later ranges' bounds intact.
+        Some(RowSet {
+            row_or_row_ranges: Some(RowOrRowRanges::RowRanges(ranges)),
+        }) => RowSet {
+            row_or_row_ranges: Some(RowOrRowRanges::RowRanges(
+                ranges
+                    .into_iter()
+                    .filter_map(|range| resume_row_range(range, last_committed_row_key))
+                    .collect(),
+            )),
+        },
+        // An explicit list of row keys: only the keys after the cursor are still outstanding.
+        Some(RowSet {
+            row_or_row_ranges: Some(RowOrRowRanges::RowKeys(keys)),
+        }) => RowSet {
+            row_or_row_ranges: Some(RowOrRowRanges::RowKeys(
+                keys.into_iter()
+                    .filter(|k| k.as_slice() > last_committed_row_key.as_slice())
+                    .collect(),
+            )),
+        },
+    });
+
+    Some(request)
+}
+
+/// Issues `ReadRows` via `issue_rpc`, decoding the response stream as in
+/// [`decode_read_rows_response`]. On a retriable gRPC error the RPC is re-issued with an
+/// exponential backoff (~100ms initial, doubling, capped at 30s) and the request's `RowSet` is
+/// rebuilt to resume strictly after the last row that was fully committed, so no row is ever
+/// re-emitted and a scan already in progress doesn't restart from the beginning. A non-retriable
+/// error (e.g. invalid argument) is returned immediately.
+///
+/// `issue_rpc` performs the actual RPC call (re-acquiring a channel/client as needed) and is
+/// expected to return the response stream for the given request.
+pub async fn read_rows_with_retry<F, Fut>(
+    mut request: ReadRowsRequest,
+    timeout: &Option<Duration>,
+    decompress: bool,
+    mut issue_rpc: F,
+) -> Result<Vec<(RowKey, Vec<RowCell>)>>
+where
+    F: FnMut(ReadRowsRequest) -> Fut,
+    Fut: Future<Output = std::result::Result<Streaming<ReadRowsResponse>, Status>>,
+{
+    let mut rows: Vec<(RowKey, Vec<RowCell>)> = vec![];
+    let mut decoder = RowChunkDecoder::default();
+    // Resumed requests are always rebuilt from the caller's request, since `rows_emitted`
+    // counts every row committed since the scan began.
+    let original_request = request.clone();
+    let mut backoff = ExponentialBackoff {
+        initial_interval: Duration::from_millis(100),
+        multiplier: 2.0,
+        max_interval: Duration::from_secs(30),
+        max_elapsed_time: Some(Duration::from_secs(5 * 60)),
+        ..ExponentialBackoff::default()
+    };
+
+    loop {
+        let mut rrr = match issue_rpc(request.clone()).await {
+            Ok(rrr) => rrr,
+            Err(status) if is_retriable(&status) => match backoff.next_backoff() {
+                Some(delay) => {
+                    debug!("ReadRows failed with {status}, retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+                None => return Err(status.into()),
+            },
+            Err(status) => return Err(status.into()),
+        };
+
+        let started = Instant::now();
+        // Only a retriable `tonic::Status` from the stream itself ends up here. A decode error
+        // from `decoder.feed` (e.g. `Error::DecompressionError`) is deterministic data
+        // corruption, not a transient network blip, so it's returned immediately via `?` below
+        // instead of being funneled into the backoff-and-retry handling.
+        let stream_result: std::result::Result<(), Status> = loop {
+            let next = match rrr.message().await {
+                Ok(next) => next,
+                Err(status) if is_retriable(&status) => break Err(status),
+                Err(status) => return Err(status.into()),
+            };
+            let Some(res) = next else {
+                return Ok(rows);
+            };
+            if let Some(timeout) = timeout.as_ref() {
+                if Instant::now().duration_since(started) > *timeout {
+                    return Err(Error::TimeoutError(timeout.as_secs()));
+                }
+            }
+            decoder.feed(res, decompress, &mut rows)?;
+        };
+
+        if let Err(status) = stream_result {
+            match backoff.next_backoff() {
+                Some(delay) => {
+                    debug!("ReadRows stream failed with {status}, retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    decoder.discard_partial_row();
+                    match decoder.last_committed_row_key.clone() {
+                        // Nothing has committed yet, so there's no cursor to resume from --
+                        // just re-issue the same request unmodified, exactly like the
+                        // `issue_rpc` retry above. This is the common case of a scan failing
+                        // before its first row ever commits.
+                        None => {}
+                        Some(last_committed_row_key) => {
+                            match resume_request(
+                                original_request.clone(),
+                                &last_committed_row_key,
+                                decoder.rows_emitted,
+                            ) {
+                                Some(resumed) => request = resumed,
+                                // The original rows_limit was already met before the failure;
+                                // there's nothing left to resume.
+                                None => return Ok(rows),
+                            }
+                        }
+                    }
+                }
+                None => return Err(status.into()),
+            }
+        }
+    }
+}
+
+/// Deserializes `cell.value` as `bincode` into `T`.
+///
+/// Downstream users almost always reinterpret a `RowCell`'s raw bytes into a concrete type;
+/// this is the shared glue for that, so callers don't each hand-roll it over the output of
+/// [`decode_read_rows_response`] or [`read_rows_stream`]. Kept separate from
+/// [`deserialize_cell_protobuf`] (rather than one function generic over both formats) so an
+/// ordinary bincode-shaped struct never needs to implement `prost::Message` just to be usable
+/// here. Note this is a deliberate departure from a single `deserialize_cell<T>(cell, format)`
+/// entry point: a shared function would have to bound `T` on both `DeserializeOwned` and
+/// `Message + Default` at once, which pushes that requirement onto every caller regardless of
+/// which format they actually use.
+pub fn deserialize_cell_bincode<T>(cell: &RowCell) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    bincode::deserialize(&cell.value).map_err(|e| Error::ObjectCorrupt(format!("bincode: {e}")))
+}
+
+/// Deserializes `cell.value` as a `prost`-encoded protobuf message into `T`.
+///
+/// See [`deserialize_cell_bincode`] for why this is a separate function rather than one
+/// generic over both wire formats.
+pub fn deserialize_cell_protobuf<T>(cell: &RowCell) -> Result<T>
+where
+    T: Message + Default,
+{
+    T::decode(cell.value.as_slice()).map_err(|e| Error::ObjectCorrupt(format!("protobuf: {e}")))
+}
+
+/// Maps a full set of decoded rows into `(RowKey, T)` pairs by bincode-deserializing the cell
+/// named `qualifier` out of each row's cells.
+///
+/// A row missing `qualifier` entirely is dropped rather than treated as an error, since a
+/// sparse column family commonly means "this row doesn't have that data".
+pub fn deserialize_rows_bincode<T>(
+    rows: Vec<(RowKey, Vec<RowCell>)>,
+    qualifier: &[u8],
+) -> Result<Vec<(RowKey, T)>>
+where
+    T: DeserializeOwned,
+{
+    rows.into_iter()
+        .filter_map(|(row_key, cells)| {
+            cells
+                .iter()
+                .find(|cell| cell.qualifier == qualifier)
+                .map(|cell| deserialize_cell_bincode(cell).map(|value| (row_key, value)))
+        })
+        .collect()
+}
+
+/// Maps a full set of decoded rows into `(RowKey, T)` pairs by protobuf-deserializing the cell
+/// named `qualifier` out of each row's cells. See [`deserialize_rows_bincode`] for the handling
+/// of rows missing that qualifier.
+pub fn deserialize_rows_protobuf<T>(
+    rows: Vec<(RowKey, Vec<RowCell>)>,
+    qualifier: &[u8],
+) -> Result<Vec<(RowKey, T)>>
+where
+    T: Message + Default,
+{
+    rows.into_iter()
+        .filter_map(|(row_key, cells)| {
+            cells
+                .iter()
+                .find(|cell| cell.qualifier == qualifier)
+                .map(|cell| deserialize_cell_protobuf(cell).map(|value| (row_key, value)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::google::bigtable::v2::read_rows_response::CellChunk;
+
+    fn chunk(
+        row_key: &[u8],
+        qualifier: Option<&[u8]>,
+        value: &[u8],
+        row_status: Option<RowStatus>,
+    ) -> CellChunk {
+        CellChunk {
+            row_key: row_key.to_vec(),
+            family_name: qualifier.map(|_| "cf".to_owned()),
+            qualifier: qualifier.map(|q| q.to_vec()),
+            timestamp_micros: 1000,
+            value: value.to_vec(),
+            row_status,
+            ..Default::default()
+        }
+    }
+
+    fn response(chunks: Vec<CellChunk>) -> ReadRowsResponse {
+        ReadRowsResponse {
+            chunks,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn feed_reassembles_a_single_committed_cell() {
+        let mut decoder = RowChunkDecoder::default();
+        let mut rows = vec![];
+        let res = response(vec![chunk(
+            b"row1",
+            Some(b"qual1"),
+            b"value1",
+            Some(RowStatus::CommitRow(true)),
+        )]);
+
+        decoder.feed(res, false, &mut rows).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        let (row_key, cells) = &rows[0];
+        assert_eq!(*row_key, b"row1".to_vec());
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].qualifier, b"qual1".to_vec());
+        assert_eq!(cells[0].value, b"value1".to_vec());
+        assert_eq!(decoder.last_committed_row_key, Some(b"row1".to_vec()));
+        assert_eq!(decoder.rows_emitted, 1);
+    }
+
+    #[test]
+    fn feed_reassembles_a_cell_value_split_across_chunks() {
+        let mut decoder = RowChunkDecoder::default();
+        let mut rows = vec![];
+        let res = response(vec![
+            chunk(b"row1", Some(b"qual1"), b"val", None),
+            chunk(b"", None, b"ue1", Some(RowStatus::CommitRow(true))),
+        ]);
+
+        decoder.feed(res, false, &mut rows).unwrap();
+
+        assert_eq!(rows[0].1[0].value, b"value1".to_vec());
+    }
+
+    #[test]
+    fn feed_reset_row_drops_uncommitted_state() {
+        let mut decoder = RowChunkDecoder::default();
+        let mut rows = vec![];
+        let res = response(vec![
+            chunk(b"row1", Some(b"qual1"), b"stale", None),
+            chunk(b"", None, b"", Some(RowStatus::ResetRow(true))),
+        ]);
+
+        decoder.feed(res, false, &mut rows).unwrap();
+
+        assert!(rows.is_empty());
+        assert!(decoder.row_key.is_none());
+        assert!(decoder.row_data.is_empty());
+        assert!(decoder.cell_name.is_none());
+        assert!(decoder.cell_value.is_empty());
+    }
+
+    #[test]
+    fn discard_partial_row_clears_in_progress_cell_state() {
+        let mut decoder = RowChunkDecoder::default();
+        let mut rows = vec![];
+        let res = response(vec![chunk(b"row1", Some(b"qual1"), b"partial", None)]);
+        decoder.feed(res, false, &mut rows).unwrap();
+        assert!(decoder.cell_name.is_some());
+
+        decoder.discard_partial_row();
+
+        assert!(decoder.row_key.is_none());
+        assert!(decoder.row_data.is_empty());
+        assert!(decoder.cell_name.is_none());
+        assert!(decoder.cell_family_name.is_none());
+        assert!(decoder.cell_value.is_empty());
+    }
+
+    #[test]
+    fn resume_row_range_narrows_the_in_progress_range() {
+        let range = RowRange {
+            start_key: Some(StartKey::StartKeyClosed(b"a".to_vec())),
+            end_key: Some(EndKey::EndKeyOpen(b"z".to_vec())),
+        };
+
+        let resumed = resume_row_range(range, &b"m".to_vec()).unwrap();
+
+        assert_eq!(
+            resumed.start_key,
+            Some(StartKey::StartKeyOpen(b"m".to_vec()))
+        );
+        assert_eq!(resumed.end_key, Some(EndKey::EndKeyOpen(b"z".to_vec())));
+    }
+
+    #[test]
+    fn resume_row_range_drops_ranges_already_passed() {
+        let range = RowRange {
+            start_key: Some(StartKey::StartKeyClosed(b"a".to_vec())),
+            end_key: Some(EndKey::EndKeyOpen(b"f".to_vec())),
+        };
+
+        assert!(resume_row_range(range, &b"m".to_vec()).is_none());
+    }
+
+    #[test]
+    fn resume_row_range_keeps_ranges_entirely_ahead_of_the_cursor() {
+        let range = RowRange {
+            start_key: Some(StartKey::StartKeyClosed(b"p".to_vec())),
+            end_key: Some(EndKey::EndKeyOpen(b"z".to_vec())),
+        };
+
+        let resumed = resume_row_range(range.clone(), &b"m".to_vec()).unwrap();
+
+        assert_eq!(resumed, range);
+    }
+
+    #[test]
+    fn resume_request_trims_multi_range_row_set() {
+        let request = ReadRowsRequest {
+            rows: Some(RowSet {
+                row_or_row_ranges: Some(RowOrRowRanges::RowRanges(vec![
+                    RowRange {
+                        start_key: Some(StartKey::StartKeyClosed(b"a".to_vec())),
+                        end_key: Some(EndKey::EndKeyOpen(b"f".to_vec())),
+                    },
+                    RowRange {
+                        start_key: Some(StartKey::StartKeyClosed(b"g".to_vec())),
+                        end_key: Some(EndKey::EndKeyOpen(b"z".to_vec())),
+                    },
+                ])),
+            }),
+            ..Default::default()
+        };
+
+        let resumed = resume_request(request, &b"c".to_vec(), 1).unwrap();
+        let ranges = match resumed.rows.unwrap().row_or_row_ranges.unwrap() {
+            RowOrRowRanges::RowRanges(ranges) => ranges,
+            _ => panic!("expected RowRanges"),
+        };
+
+        // The first range (already in progress) is narrowed to resume after the cursor; the
+        // second range, entirely ahead of it, survives untouched.
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(
+            ranges[0].start_key,
+            Some(StartKey::StartKeyOpen(b"c".to_vec()))
+        );
+        assert_eq!(
+            ranges[1].start_key,
+            Some(StartKey::StartKeyClosed(b"g".to_vec()))
+        );
+    }
+
+    #[test]
+    fn resume_request_keeps_only_row_keys_after_the_cursor() {
+        let request = ReadRowsRequest {
+            rows: Some(RowSet {
+                row_or_row_ranges: Some(RowOrRowRanges::RowKeys(vec![
+                    b"a".to_vec(),
+                    b"m".to_vec(),
+                    b"z".to_vec(),
+                ])),
+            }),
+            ..Default::default()
+        };
+
+        let resumed = resume_request(request, &b"m".to_vec(), 1).unwrap();
+        let keys = match resumed.rows.unwrap().row_or_row_ranges.unwrap() {
+            RowOrRowRanges::RowKeys(keys) => keys,
+            _ => panic!("expected RowKeys"),
+        };
+
+        assert_eq!(keys, vec![b"z".to_vec()]);
+    }
+
+    #[test]
+    fn resume_request_returns_none_once_rows_limit_is_exhausted() {
+        let request = ReadRowsRequest {
+            rows_limit: 2,
+            ..Default::default()
+        };
+
+        assert!(resume_request(request, &b"a".to_vec(), 2).is_none());
+    }
+
+    #[test]
+    fn resume_request_decrements_rows_limit_without_flooring_at_one() {
+        let request = ReadRowsRequest {
+            rows_limit: 5,
+            ..Default::default()
+        };
+        let resumed = resume_request(request, &b"a".to_vec(), 4).unwrap();
+        assert_eq!(resumed.rows_limit, 1);
+
+        let request = ReadRowsRequest {
+            rows_limit: 5,
+            ..Default::default()
+        };
+        assert!(resume_request(request, &b"a".to_vec(), 5).is_none());
+    }
 }